@@ -2,15 +2,24 @@
 //!
 //! Constructs context-aware prompts for the LLM based on telemetry.
 
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
 use crate::ollama::OllamaClient;
+use crate::storage::{Storage, StorageError};
+use crate::streaming::{StreamChunk, StreamError, StreamHandle, StreamingOllama};
 use crate::telemetry::Telemetry;
 
 /// ATC Engine - manages the conversation and prompt construction
 pub struct AtcEngine {
     ollama: OllamaClient,
+    streaming: StreamingOllama,
     conversation_history: Vec<ConversationEntry>,
     callsign: String,
     aircraft_type: String,
+    storage: Option<Arc<Storage>>,
+    session_id: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -31,18 +40,58 @@ impl AtcEngine {
     pub fn new(callsign: impl Into<String>, aircraft_type: impl Into<String>) -> Self {
         Self {
             ollama: OllamaClient::default(),
+            streaming: StreamingOllama::default(),
             conversation_history: Vec::new(),
             callsign: callsign.into(),
             aircraft_type: aircraft_type.into(),
+            storage: None,
+            session_id: None,
         }
     }
-    
+
     /// Set the Ollama model
     pub fn with_model(mut self, model: impl Into<String>) -> Self {
-        self.ollama = OllamaClient::new(model);
+        let model = model.into();
+        self.ollama = OllamaClient::new(model.clone());
+        self.streaming = StreamingOllama::new(model);
         self
     }
-    
+
+    /// Attach a SQLite-backed store and start a new persisted session.
+    /// Every subsequent pilot/ATC exchange is written through to it.
+    pub async fn with_storage(mut self, storage: Arc<Storage>) -> Result<Self, StorageError> {
+        let session_id = storage.start_session(&self.callsign, &self.aircraft_type).await?;
+        self.storage = Some(storage);
+        self.session_id = Some(session_id);
+        Ok(self)
+    }
+
+    /// Reload a prior session's transcript into the in-memory rolling
+    /// window and continue persisting new entries to it.
+    pub async fn load_session(
+        &mut self,
+        storage: Arc<Storage>,
+        session_id: i64,
+    ) -> Result<(), StorageError> {
+        self.conversation_history = storage.load_transcript(session_id).await?;
+        self.storage = Some(storage);
+        self.session_id = Some(session_id);
+        Ok(())
+    }
+
+    /// Query a longer history than the in-memory rolling window keeps, for
+    /// prompts that want more than the last 10 exchanges. Falls back to the
+    /// in-memory window if no store is attached.
+    pub async fn recent_history(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<ConversationEntry>, StorageError> {
+        match (&self.storage, self.session_id) {
+            (Some(storage), Some(session_id)) => storage.recent_entries(session_id, limit).await,
+            _ => Ok(self.conversation_history.clone()),
+        }
+    }
+
     /// Build the ATC system prompt
     fn build_system_prompt(&self, telemetry: &Telemetry) -> String {
         let altitude_ft = (telemetry.position.altitude_msl_m * 3.28084) as i32;
@@ -88,38 +137,108 @@ Respond ONLY with what ATC would say. No explanations."#,
         telemetry: &Telemetry,
     ) -> Result<String, crate::ollama::OllamaError> {
         // Add pilot message to history
-        self.conversation_history.push(ConversationEntry {
+        let pilot_entry = ConversationEntry {
             speaker: Speaker::Pilot,
             message: pilot_message.to_string(),
             timestamp: chrono::Utc::now().timestamp(),
-        });
-        
+        };
+        self.conversation_history.push(pilot_entry.clone());
+        self.persist_entry(&pilot_entry, Some(telemetry)).await;
+
         // Build the full prompt
         let system_prompt = self.build_system_prompt(telemetry);
         let history = self.format_history();
-        
+
         let full_prompt = format!(
             "{system_prompt}\n\nCONVERSATION:\n{history}\nPILOT: {pilot_message}\nATC:",
         );
-        
+
         // Get LLM response
         let response = self.ollama.generate(&full_prompt).await?;
         let response = response.trim().to_string();
-        
+
         // Add ATC response to history
-        self.conversation_history.push(ConversationEntry {
+        let atc_entry = ConversationEntry {
             speaker: Speaker::Atc,
             message: response.clone(),
             timestamp: chrono::Utc::now().timestamp(),
-        });
-        
+        };
+        self.conversation_history.push(atc_entry.clone());
+        self.persist_entry(&atc_entry, None).await;
+
         // Keep history manageable (last 10 exchanges)
         if self.conversation_history.len() > 20 {
             self.conversation_history.drain(0..2);
         }
-        
+
         Ok(response)
     }
+
+    /// Begin a streaming pilot exchange ("barge-in" capable).
+    ///
+    /// Records the pilot's message immediately and returns a `StreamHandle`
+    /// (call `.interrupt()` if the pilot keys the mic again before the
+    /// response finishes) alongside the chunk receiver. The caller drains
+    /// the receiver and then calls `finish_streaming_response` with
+    /// whatever text it collected, so the exchange gets recorded the same
+    /// way the non-streaming path does.
+    pub async fn start_pilot_input_streaming(
+        &mut self,
+        pilot_message: &str,
+        telemetry: &Telemetry,
+    ) -> Result<(StreamHandle, mpsc::Receiver<Result<StreamChunk, StreamError>>), StreamError> {
+        let pilot_entry = ConversationEntry {
+            speaker: Speaker::Pilot,
+            message: pilot_message.to_string(),
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        self.conversation_history.push(pilot_entry.clone());
+        self.persist_entry(&pilot_entry, Some(telemetry)).await;
+
+        let system_prompt = self.build_system_prompt(telemetry);
+        let history = self.format_history();
+        let full_prompt = format!(
+            "{system_prompt}\n\nCONVERSATION:\n{history}\nPILOT: {pilot_message}\nATC:",
+        );
+
+        self.streaming.generate_stream(&full_prompt).await
+    }
+
+    /// Record the ATC side of a streaming exchange once the caller has
+    /// drained the receiver from `start_pilot_input_streaming`.
+    /// `interrupted` should reflect whatever the final `StreamChunk`
+    /// reported, so a barge-in-truncated transmission is recorded as such
+    /// rather than a complete one.
+    pub async fn finish_streaming_response(&mut self, response: &str, interrupted: bool) {
+        let message = if interrupted {
+            format!("{} [interrupted]", response.trim())
+        } else {
+            response.trim().to_string()
+        };
+
+        let atc_entry = ConversationEntry {
+            speaker: Speaker::Atc,
+            message,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        self.conversation_history.push(atc_entry.clone());
+        self.persist_entry(&atc_entry, None).await;
+
+        if self.conversation_history.len() > 20 {
+            self.conversation_history.drain(0..2);
+        }
+    }
+
+    /// Write an entry to the SQLite store, if one is attached. A
+    /// persistence failure is logged but doesn't interrupt the exchange -
+    /// the in-memory window keeps working even if the database is down.
+    async fn persist_entry(&self, entry: &ConversationEntry, telemetry: Option<&Telemetry>) {
+        if let (Some(storage), Some(session_id)) = (&self.storage, self.session_id) {
+            if let Err(e) = storage.record_entry(session_id, entry, telemetry).await {
+                tracing::warn!("Failed to persist conversation entry: {e}");
+            }
+        }
+    }
     
     /// Format conversation history for the prompt
     fn format_history(&self) -> String {
@@ -140,6 +259,15 @@ Respond ONLY with what ATC would say. No explanations."#,
     pub fn history(&self) -> &[ConversationEntry] {
         &self.conversation_history
     }
+
+    /// Replace the in-memory conversation window wholesale. Used during a
+    /// sector handoff so the receiving controller inherits the pilot's
+    /// context instead of starting cold; does not touch persisted storage,
+    /// since the entries were already recorded by whichever engine handled
+    /// them originally.
+    pub fn adopt_history(&mut self, history: Vec<ConversationEntry>) {
+        self.conversation_history = history;
+    }
     
     /// Clear conversation history
     pub fn clear_history(&mut self) {
@@ -6,16 +6,28 @@
 //! - Streaming: Low-latency streaming LLM responses
 //! - Warmup: Keep model hot to eliminate cold-starts
 //! - ATC: Prompt building and response parsing
+//! - Otel: Optional OpenTelemetry OTLP tracing/metrics export
+//! - Transport: Pluggable telemetry/command transport (file, optionally NATS)
+//! - Storage: SQLite-backed conversation/telemetry persistence
+//! - Registry: Multi-sector `ControllerRegistry` with automatic handoff
 
 pub mod atc;
 pub mod ollama;
+pub mod otel;
+pub mod registry;
+pub mod storage;
 pub mod telemetry;
 pub mod streaming;
+pub mod transport;
 pub mod warmup;
 
 // Re-export common types
 pub use atc::AtcEngine;
 pub use ollama::OllamaClient;
+pub use otel::OtelConfig;
+pub use registry::{AirspaceState, ControllerRegistry, HandoffDirection, SectorConfig};
+pub use storage::{Storage, StorageError};
 pub use telemetry::{TelemetryWatcher, Telemetry};
-pub use streaming::{StreamChunk, StreamingOllama};
-pub use warmup::{WarmupConfig, WarmupService};
+pub use streaming::{StreamChunk, StreamHandle, StreamingOllama};
+pub use transport::{Command, CommandSink, FileTransport, TelemetrySource};
+pub use warmup::{WarmupConfig, WarmupService, WarmupStats};
@@ -2,9 +2,13 @@
 //!
 //! Communicates with Ollama REST API for ATC response generation.
 
+use futures_util::TryStreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio::io::AsyncBufReadExt;
+use tokio::sync::mpsc;
+use tokio_util::io::StreamReader;
 
 #[derive(Error, Debug)]
 pub enum OllamaError {
@@ -25,10 +29,26 @@ struct GenerateRequest {
     options: GenerateOptions,
 }
 
-#[derive(Debug, Serialize)]
-struct GenerateOptions {
-    temperature: f32,
-    num_predict: i32,
+/// Generation parameters forwarded to Ollama's `options` object.
+///
+/// `num_ctx` controls the context window. Ollama has no API to query a
+/// model's max context, so the caller must set it explicitly - we default
+/// to 4096 which fits comfortably on a 3B-class model without swapping.
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerateOptions {
+    pub temperature: f32,
+    pub num_predict: i32,
+    pub num_ctx: i32,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self {
+            temperature: 0.7,
+            num_predict: 256,
+            num_ctx: 4096,
+        }
+    }
 }
 
 /// Response from Ollama generate endpoint
@@ -38,11 +58,39 @@ struct GenerateResponse {
     done: bool,
 }
 
+/// A single entry from `/api/tags`, describing an installed model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OllamaModel {
+    pub name: String,
+    pub size: u64,
+    pub digest: String,
+    pub modified_at: String,
+}
+
+/// Response body for `/api/tags`
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    models: Vec<OllamaModel>,
+}
+
+/// One line of an Ollama streaming `/api/generate` response.
+///
+/// Ollama can interleave an `{"error": "..."}` object instead of a normal
+/// chunk (e.g. if the model unloads mid-stream), so this is untagged rather
+/// than a single struct - each line is tried against both shapes.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum GenerateStreamLine {
+    Chunk { response: String, done: bool },
+    Err { error: String },
+}
+
 /// Ollama client for ATC response generation
 pub struct OllamaClient {
     client: Client,
     base_url: String,
     model: String,
+    options: GenerateOptions,
 }
 
 impl OllamaClient {
@@ -52,15 +100,60 @@ impl OllamaClient {
             client: Client::new(),
             base_url: "http://localhost:11434".to_string(),
             model: model.into(),
+            options: GenerateOptions::default(),
         }
     }
-    
+
     /// Set custom Ollama URL
     pub fn with_url(mut self, url: impl Into<String>) -> Self {
         self.base_url = url.into();
         self
     }
-    
+
+    /// Set the context window size (`num_ctx`), in tokens
+    pub fn with_num_ctx(mut self, num_ctx: i32) -> Self {
+        self.options.num_ctx = num_ctx;
+        self
+    }
+
+    /// Set the sampling temperature
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.options.temperature = temperature;
+        self
+    }
+
+    /// Set the max number of tokens to predict
+    pub fn with_num_predict(mut self, num_predict: i32) -> Self {
+        self.options.num_predict = num_predict;
+        self
+    }
+
+    /// Switch the active model at runtime
+    pub fn set_model(&mut self, model: impl Into<String>) {
+        self.model = model.into();
+    }
+
+    /// The currently active model
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// List models installed in the local Ollama instance
+    pub async fn list_models(&self) -> Result<Vec<OllamaModel>, OllamaError> {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(OllamaError::NotAvailable);
+        }
+
+        let tags: TagsResponse = response.json().await?;
+        Ok(tags.models)
+    }
+
     /// Check if Ollama is available
     pub async fn is_available(&self) -> bool {
         self.client
@@ -69,33 +162,115 @@ impl OllamaClient {
             .await
             .is_ok()
     }
-    
+
     /// Generate a response from the LLM
+    #[tracing::instrument(skip(self, prompt), fields(model = %self.model, prompt_len = prompt.len(), response_chars = tracing::field::Empty))]
     pub async fn generate(&self, prompt: &str) -> Result<String, OllamaError> {
+        let start = std::time::Instant::now();
+
         let request = GenerateRequest {
             model: self.model.clone(),
             prompt: prompt.to_string(),
             stream: false,
-            options: GenerateOptions {
-                temperature: 0.7,
-                num_predict: 256,
-            },
+            options: self.options.clone(),
         };
-        
+
         let response = self.client
             .post(format!("{}/api/generate", self.base_url))
             .json(&request)
             .timeout(std::time::Duration::from_secs(30))
             .send()
             .await?;
-        
+
         if !response.status().is_success() {
             return Err(OllamaError::NotAvailable);
         }
-        
+
         let result: GenerateResponse = response.json().await?;
+
+        let latency_ms = start.elapsed().as_millis() as u64;
+        tracing::Span::current().record("response_chars", result.response.len());
+        crate::otel::record_latency_ms("ollama_generate_latency_ms", &self.model, latency_ms);
+
         Ok(result.response)
     }
+
+    /// Generate a response, streaming tokens as they arrive.
+    ///
+    /// Sends `stream: true` and reads the response body as newline-delimited
+    /// JSON via `AsyncBufReadExt::lines()`. Each line is either a normal
+    /// `{"response", "done"}` chunk or an `{"error"}` object; the latter is
+    /// surfaced as `OllamaError::InvalidResponse` rather than silently
+    /// dropped, so the caller (e.g. the `streaming` module) can stop and
+    /// report it instead of hanging.
+    pub async fn generate_stream(
+        &self,
+        prompt: &str,
+    ) -> Result<mpsc::Receiver<Result<String, OllamaError>>, OllamaError> {
+        let request = GenerateRequest {
+            model: self.model.clone(),
+            prompt: prompt.to_string(),
+            stream: true,
+            options: self.options.clone(),
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/api/generate", self.base_url))
+            .json(&request)
+            .timeout(std::time::Duration::from_secs(30))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(OllamaError::NotAvailable);
+        }
+
+        let byte_stream = response
+            .bytes_stream()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
+        let mut lines = StreamReader::new(byte_stream).lines();
+
+        let (tx, rx) = mpsc::channel(32);
+
+        tokio::spawn(async move {
+            loop {
+                let line = match lines.next_line().await {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(e) => {
+                        let _ = tx.send(Err(OllamaError::InvalidResponse(e.to_string()))).await;
+                        break;
+                    }
+                };
+
+                if line.is_empty() {
+                    continue;
+                }
+
+                match serde_json::from_str::<GenerateStreamLine>(&line) {
+                    Ok(GenerateStreamLine::Chunk { response, done }) => {
+                        if tx.send(Ok(response)).await.is_err() {
+                            break;
+                        }
+                        if done {
+                            break;
+                        }
+                    }
+                    Ok(GenerateStreamLine::Err { error }) => {
+                        let _ = tx.send(Err(OllamaError::InvalidResponse(error))).await;
+                        break;
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(OllamaError::InvalidResponse(e.to_string()))).await;
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
 }
 
 impl Default for OllamaClient {
@@ -0,0 +1,145 @@
+//! OpenTelemetry observability subsystem
+//!
+//! Optional OTLP export of traces and latency histograms, gated behind the
+//! `otel` cargo feature so a build that doesn't need a collector avoids the
+//! extra dependencies and runtime cost. The `tracing` spans themselves
+//! (`send_heartbeat`, `OllamaClient::generate`, `read_telemetry_file`) are
+//! always present; this module only controls where they're exported.
+
+/// Configuration for the OTLP exporter
+#[derive(Debug, Clone)]
+pub struct OtelConfig {
+    pub endpoint: String,
+    pub service_name: String,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://localhost:4317".to_string(),
+            service_name: "stratus-atc".to_string(),
+        }
+    }
+}
+
+impl OtelConfig {
+    /// Build a config from the environment, so the exporter endpoint can be
+    /// pointed at a collector during a tuning session without a code
+    /// change: `STRATUS_OTEL_ENDPOINT` / `STRATUS_OTEL_SERVICE_NAME`.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            endpoint: std::env::var("STRATUS_OTEL_ENDPOINT").unwrap_or(defaults.endpoint),
+            service_name: std::env::var("STRATUS_OTEL_SERVICE_NAME")
+                .unwrap_or(defaults.service_name),
+        }
+    }
+}
+
+/// Initialize the OTLP trace and metrics exporters and layer the tracer onto
+/// the existing `tracing_subscriber` setup. Call once at startup, before any
+/// spans or metrics that should be exported are recorded.
+///
+/// Wires up *both* pipelines because they're independent in the SDK: the
+/// trace pipeline only gets spans flowing to the collector, while
+/// `record_latency_ms`/`record_counter` go through `global::meter(...)`,
+/// which silently resolves to a no-op meter unless a `MeterProvider` has
+/// also been installed via `global::set_meter_provider`.
+#[cfg(feature = "otel")]
+pub fn init(config: &OtelConfig) -> anyhow::Result<()> {
+    use opentelemetry::{global, KeyValue};
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{runtime, trace as sdktrace, Resource};
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+    let resource = Resource::new(vec![KeyValue::new(
+        "service.name",
+        config.service_name.clone(),
+    )]);
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&config.endpoint),
+        )
+        .with_trace_config(sdktrace::config().with_resource(resource.clone()))
+        .install_batch(runtime::Tokio)?;
+
+    let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .with(otel_layer)
+        .try_init()?;
+
+    let meter_provider = opentelemetry_otlp::new_pipeline()
+        .metrics(runtime::Tokio)
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&config.endpoint),
+        )
+        .with_resource(resource)
+        .build()?;
+    global::set_meter_provider(meter_provider);
+
+    Ok(())
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn init(_config: &OtelConfig) -> anyhow::Result<()> {
+    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::EnvFilter::from_default_env())
+        .try_init()?;
+
+    Ok(())
+}
+
+/// Flush and shut down the OTLP pipeline. Call once on application exit.
+#[cfg(feature = "otel")]
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn shutdown() {}
+
+/// Record a latency sample (in milliseconds) against a named histogram,
+/// tagged with the model that produced it.
+///
+/// Used for cold-start vs. warm latency (`warmup_heartbeat_latency_ms`) and
+/// generate latencies (`ollama_generate_latency_ms`), so both can be watched
+/// side by side in Grafana/Jaeger.
+#[cfg(feature = "otel")]
+pub fn record_latency_ms(metric_name: &'static str, model: &str, latency_ms: u64) {
+    use opentelemetry::{global, KeyValue};
+
+    global::meter("stratus-atc")
+        .u64_histogram(metric_name)
+        .init()
+        .record(latency_ms, &[KeyValue::new("model", model.to_string())]);
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn record_latency_ms(_metric_name: &'static str, _model: &str, _latency_ms: u64) {}
+
+/// Increment a named counter (e.g. total streamed chunks/tokens), tagged
+/// with the model that produced them.
+#[cfg(feature = "otel")]
+pub fn record_counter(metric_name: &'static str, model: &str, value: u64) {
+    use opentelemetry::{global, KeyValue};
+
+    global::meter("stratus-atc")
+        .u64_counter(metric_name)
+        .init()
+        .add(value, &[KeyValue::new("model", model.to_string())]);
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn record_counter(_metric_name: &'static str, _model: &str, _value: u64) {}
@@ -0,0 +1,319 @@
+//! Controller Registry - multi-sector airspace simulation
+//!
+//! `AtcEngine` models exactly one aircraft talking to exactly one
+//! controller. `ControllerRegistry` owns several `AtcEngine`s keyed by
+//! sector (ground/tower/approach/center), each tuned to its own frequency,
+//! and routes an aircraft's transmissions to whichever sector it's
+//! currently tuned to - mirroring the registry/broadcasting pattern used by
+//! clustered chat systems, but for airspace instead of chat rooms. When an
+//! aircraft climbs or descends through a sector's configured altitude
+//! threshold, the registry issues a handoff instruction and migrates its
+//! conversation context to the receiving sector's engine.
+
+use std::collections::HashMap;
+
+use crate::atc::AtcEngine;
+use crate::ollama::OllamaError;
+use crate::telemetry::Telemetry;
+
+/// Which way an aircraft must cross `SectorConfig::handoff_altitude_ft` to
+/// trigger a handoff out of that sector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandoffDirection {
+    /// Climbing through the threshold (e.g. tower -> approach -> center).
+    Climbing,
+    /// Descending through the threshold (e.g. center -> approach -> tower).
+    Descending,
+}
+
+/// A sector definition: the frequency aircraft must be tuned to in order to
+/// reach it, plus the altitude at which it hands traffic off.
+///
+/// Lateral boundary crossings aren't modeled - there's no geographic sector
+/// data in this tree (telemetry carries only aircraft position, not sector
+/// polygons) - so handoff is altitude-threshold-only, in either direction.
+#[derive(Debug, Clone)]
+pub struct SectorConfig {
+    pub name: String,
+    pub frequency_hz: i32,
+    /// Crossing this altitude (ft MSL), in `handoff_direction`, triggers a
+    /// handoff to `handoff_to`. `None` means this sector has no automatic
+    /// handoff trigger (e.g. center).
+    pub handoff_altitude_ft: Option<i32>,
+    pub handoff_direction: HandoffDirection,
+    pub handoff_to: Option<String>,
+}
+
+/// Airspace state shared across sectors, so e.g. approach can see which
+/// sector is currently working a given aircraft.
+#[derive(Debug, Clone, Default)]
+pub struct AirspaceState {
+    /// Aircraft callsign -> sector name currently working it
+    pub aircraft_sector: HashMap<String, String>,
+    /// Aircraft callsign -> sector it was just handed off *from*. The
+    /// aircraft's radio may still show that sector's frequency until the
+    /// pilot actually retunes, so routing sticks with the post-handoff
+    /// sector (not a stale frequency match) until the retune is observed.
+    pending_retune_from: HashMap<String, String>,
+}
+
+/// Owns one `AtcEngine` per sector and routes traffic between them
+pub struct ControllerRegistry {
+    sectors: HashMap<String, SectorConfig>,
+    engines: HashMap<String, AtcEngine>,
+    airspace: AirspaceState,
+}
+
+impl ControllerRegistry {
+    pub fn new() -> Self {
+        Self {
+            sectors: HashMap::new(),
+            engines: HashMap::new(),
+            airspace: AirspaceState::default(),
+        }
+    }
+
+    /// Register a sector/frequency and the `AtcEngine` that works it
+    pub fn add_sector(&mut self, config: SectorConfig, engine: AtcEngine) {
+        self.engines.insert(config.name.clone(), engine);
+        self.sectors.insert(config.name.clone(), config);
+    }
+
+    /// The shared airspace state (which sector is working which aircraft)
+    pub fn airspace(&self) -> &AirspaceState {
+        &self.airspace
+    }
+
+    fn sector_for_frequency(&self, com1_hz: i32) -> Option<&SectorConfig> {
+        self.sectors.values().find(|s| s.frequency_hz == com1_hz)
+    }
+
+    /// Decide which sector a transmission on `com1_hz` should route to,
+    /// reconciling the raw frequency lookup against a pending handoff: if
+    /// the aircraft was just handed off and `com1_hz` still matches the
+    /// sector it left (pilot hasn't retuned yet), stick with the sector the
+    /// registry already migrated it to instead of routing back to the one
+    /// it just left.
+    fn resolve_sector(&mut self, callsign: &str, com1_hz: i32) -> Option<String> {
+        let freq_sector = self.sector_for_frequency(com1_hz).map(|s| s.name.clone());
+
+        let stale_sector = self.airspace.pending_retune_from.get(callsign).cloned();
+        let still_on_stale_freq = matches!(
+            (&stale_sector, &freq_sector),
+            (Some(stale), Some(freq)) if stale == freq
+        );
+
+        if still_on_stale_freq {
+            self.airspace.aircraft_sector.get(callsign).cloned()
+        } else {
+            if stale_sector.is_some() {
+                self.airspace.pending_retune_from.remove(callsign);
+            }
+            freq_sector
+        }
+    }
+
+    /// Route a pilot transmission to the sector working the aircraft,
+    /// issuing an automatic handoff first if the aircraft has just crossed
+    /// its current sector's handoff threshold.
+    ///
+    /// Routing normally follows `telemetry.radios.com1_hz`, but right after
+    /// a handoff the aircraft's radio may still show the *old* sector's
+    /// frequency (the pilot hasn't retuned yet) - in that window we stick
+    /// with the sector the registry just migrated the aircraft to instead
+    /// of letting the stale frequency route it straight back and strand the
+    /// migrated conversation context.
+    pub async fn process_pilot_input(
+        &mut self,
+        callsign: &str,
+        pilot_message: &str,
+        telemetry: &Telemetry,
+    ) -> Result<String, OllamaError> {
+        if let Some(handoff_message) = self.maybe_handoff(callsign, telemetry) {
+            return Ok(handoff_message);
+        }
+
+        let Some(sector) = self.resolve_sector(callsign, telemetry.radios.com1_hz) else {
+            return Ok(format!(
+                "{callsign}, unable to contact - frequency not monitored"
+            ));
+        };
+
+        self.airspace
+            .aircraft_sector
+            .insert(callsign.to_string(), sector.clone());
+
+        let engine = self
+            .engines
+            .get_mut(&sector)
+            .expect("sector registered without a matching engine");
+
+        engine.process_pilot_input(pilot_message, telemetry).await
+    }
+
+    /// Check whether the aircraft's current sector should hand it off and,
+    /// if so, issue the "contact ... on ..." instruction and migrate its
+    /// conversation context to the receiving sector's engine.
+    fn maybe_handoff(&mut self, callsign: &str, telemetry: &Telemetry) -> Option<String> {
+        let current_sector_name = self.airspace.aircraft_sector.get(callsign)?.clone();
+        let current_sector = self.sectors.get(&current_sector_name)?.clone();
+
+        let altitude_ft = (telemetry.position.altitude_msl_m * 3.28084) as i32;
+        let threshold = current_sector.handoff_altitude_ft?;
+        let crossed = match current_sector.handoff_direction {
+            HandoffDirection::Climbing => altitude_ft >= threshold,
+            HandoffDirection::Descending => altitude_ft <= threshold,
+        };
+        if !crossed {
+            return None;
+        }
+
+        let next_sector_name = current_sector.handoff_to.clone()?;
+        let next_sector = self.sectors.get(&next_sector_name)?.clone();
+
+        let history = self
+            .engines
+            .get(&current_sector_name)
+            .map(|engine| engine.history().to_vec())
+            .unwrap_or_default();
+        if let Some(next_engine) = self.engines.get_mut(&next_sector_name) {
+            next_engine.adopt_history(history);
+        }
+
+        self.airspace
+            .aircraft_sector
+            .insert(callsign.to_string(), next_sector_name.clone());
+        self.airspace
+            .pending_retune_from
+            .insert(callsign.to_string(), current_sector_name);
+
+        Some(format!(
+            "{callsign}, contact {} on {:.3}",
+            next_sector.name,
+            next_sector.frequency_hz as f64 / 1_000_000.0
+        ))
+    }
+}
+
+impl Default for ControllerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atc::{ConversationEntry, Speaker};
+
+    fn telemetry_at_altitude_ft(altitude_ft: f64) -> Telemetry {
+        let mut telemetry = Telemetry::default();
+        telemetry.position.altitude_msl_m = altitude_ft / 3.28084;
+        telemetry
+    }
+
+    fn tower_sector() -> SectorConfig {
+        SectorConfig {
+            name: "tower".to_string(),
+            frequency_hz: 118_300_000,
+            handoff_altitude_ft: Some(3000),
+            handoff_direction: HandoffDirection::Climbing,
+            handoff_to: Some("approach".to_string()),
+        }
+    }
+
+    fn approach_sector() -> SectorConfig {
+        SectorConfig {
+            name: "approach".to_string(),
+            frequency_hz: 124_500_000,
+            handoff_altitude_ft: Some(2000),
+            handoff_direction: HandoffDirection::Descending,
+            handoff_to: Some("tower".to_string()),
+        }
+    }
+
+    #[test]
+    fn climbing_handoff_migrates_history_and_marks_pending_retune() {
+        let mut registry = ControllerRegistry::new();
+        registry.add_sector(tower_sector(), AtcEngine::new("N12345", "C172"));
+        registry.add_sector(approach_sector(), AtcEngine::new("N12345", "C172"));
+
+        registry
+            .airspace
+            .aircraft_sector
+            .insert("N12345".to_string(), "tower".to_string());
+        registry
+            .engines
+            .get_mut("tower")
+            .unwrap()
+            .adopt_history(vec![ConversationEntry {
+                speaker: Speaker::Pilot,
+                message: "tower, ready for departure".to_string(),
+                timestamp: 0,
+            }]);
+
+        let message = registry
+            .maybe_handoff("N12345", &telemetry_at_altitude_ft(3500.0))
+            .expect("should hand off after climbing through the threshold");
+
+        assert_eq!(message, "N12345, contact approach on 124.500");
+        assert_eq!(
+            registry.airspace.aircraft_sector.get("N12345").map(String::as_str),
+            Some("approach")
+        );
+        assert_eq!(
+            registry.airspace.pending_retune_from.get("N12345").map(String::as_str),
+            Some("tower")
+        );
+        assert_eq!(registry.engines["approach"].history().len(), 1);
+    }
+
+    #[test]
+    fn descending_handoff_only_triggers_below_threshold() {
+        let mut registry = ControllerRegistry::new();
+        registry.add_sector(approach_sector(), AtcEngine::new("N12345", "C172"));
+        registry.add_sector(tower_sector(), AtcEngine::new("N12345", "C172"));
+
+        registry
+            .airspace
+            .aircraft_sector
+            .insert("N12345".to_string(), "approach".to_string());
+
+        assert!(registry
+            .maybe_handoff("N12345", &telemetry_at_altitude_ft(2500.0))
+            .is_none());
+
+        let message = registry
+            .maybe_handoff("N12345", &telemetry_at_altitude_ft(1800.0))
+            .expect("should hand off after descending through the threshold");
+        assert_eq!(message, "N12345, contact tower on 118.300");
+    }
+
+    #[test]
+    fn stale_frequency_after_handoff_routes_to_post_handoff_sector() {
+        let mut registry = ControllerRegistry::new();
+        registry.add_sector(tower_sector(), AtcEngine::new("N12345", "C172"));
+        registry.add_sector(approach_sector(), AtcEngine::new("N12345", "C172"));
+
+        registry
+            .airspace
+            .aircraft_sector
+            .insert("N12345".to_string(), "approach".to_string());
+        registry
+            .airspace
+            .pending_retune_from
+            .insert("N12345".to_string(), "tower".to_string());
+
+        // Still tuned to tower's frequency - routing should stick with the
+        // sector the aircraft was just migrated to, not the stale one.
+        let sector = registry.resolve_sector("N12345", 118_300_000);
+        assert_eq!(sector.as_deref(), Some("approach"));
+        assert!(registry.airspace.pending_retune_from.contains_key("N12345"));
+
+        // Retunes to approach's own frequency - normal routing resumes and
+        // the pending marker clears.
+        let sector = registry.resolve_sector("N12345", 124_500_000);
+        assert_eq!(sector.as_deref(), Some("approach"));
+        assert!(!registry.airspace.pending_retune_from.contains_key("N12345"));
+    }
+}
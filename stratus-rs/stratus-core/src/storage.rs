@@ -0,0 +1,236 @@
+//! SQLite-backed persistence for conversation transcripts and telemetry
+//!
+//! `AtcEngine` used to keep `conversation_history` purely in memory,
+//! hard-truncating to the last 20 entries and losing everything once the
+//! process exited. `Storage` opens a SQLite database once at startup and
+//! durably records every entry (with a telemetry snapshot alongside the
+//! pilot's transmission), so flights can be replayed and prompts can pull
+//! a longer summarized history than the in-memory window keeps.
+
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use thiserror::Error;
+
+use crate::atc::{ConversationEntry, Speaker};
+use crate::telemetry::Telemetry;
+
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Telemetry serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// SQLite-backed store for conversation transcripts and telemetry snapshots
+pub struct Storage {
+    pool: SqlitePool,
+}
+
+impl Storage {
+    /// Open (creating if needed) the database at `path` and run migrations
+    pub async fn open(path: impl AsRef<std::path::Path>) -> Result<Self, StorageError> {
+        let url = format!("sqlite://{}?mode=rwc", path.as_ref().display());
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect(&url)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS sessions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                callsign TEXT NOT NULL,
+                aircraft_type TEXT NOT NULL,
+                started_at INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS conversation_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id INTEGER NOT NULL REFERENCES sessions(id),
+                speaker TEXT NOT NULL,
+                message TEXT NOT NULL,
+                timestamp INTEGER NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS telemetry_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                entry_id INTEGER NOT NULL REFERENCES conversation_entries(id),
+                telemetry_json TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    /// Start a new session and return its id
+    pub async fn start_session(
+        &self,
+        callsign: &str,
+        aircraft_type: &str,
+    ) -> Result<i64, StorageError> {
+        let started_at = chrono::Utc::now().timestamp();
+        let result = sqlx::query(
+            "INSERT INTO sessions (callsign, aircraft_type, started_at) VALUES (?, ?, ?)",
+        )
+        .bind(callsign)
+        .bind(aircraft_type)
+        .bind(started_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Record a conversation entry, with an optional telemetry snapshot
+    /// (typically attached to the pilot's transmission, not the reply)
+    pub async fn record_entry(
+        &self,
+        session_id: i64,
+        entry: &ConversationEntry,
+        telemetry: Option<&Telemetry>,
+    ) -> Result<(), StorageError> {
+        let speaker = speaker_label(&entry.speaker);
+
+        let result = sqlx::query(
+            "INSERT INTO conversation_entries (session_id, speaker, message, timestamp) VALUES (?, ?, ?, ?)",
+        )
+        .bind(session_id)
+        .bind(speaker)
+        .bind(&entry.message)
+        .bind(entry.timestamp)
+        .execute(&self.pool)
+        .await?;
+
+        if let Some(telemetry) = telemetry {
+            let entry_id = result.last_insert_rowid();
+            let telemetry_json = serde_json::to_string(telemetry)?;
+            sqlx::query("INSERT INTO telemetry_snapshots (entry_id, telemetry_json) VALUES (?, ?)")
+                .bind(entry_id)
+                .bind(telemetry_json)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Reload the full transcript for a prior session, oldest first
+    pub async fn load_transcript(
+        &self,
+        session_id: i64,
+    ) -> Result<Vec<ConversationEntry>, StorageError> {
+        let rows = sqlx::query(
+            "SELECT speaker, message, timestamp FROM conversation_entries WHERE session_id = ? ORDER BY id ASC",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(entry_from_row).collect())
+    }
+
+    /// Query the most recent `limit` entries for a session, oldest first
+    pub async fn recent_entries(
+        &self,
+        session_id: i64,
+        limit: i64,
+    ) -> Result<Vec<ConversationEntry>, StorageError> {
+        let rows = sqlx::query(
+            "SELECT speaker, message, timestamp FROM conversation_entries WHERE session_id = ? ORDER BY id DESC LIMIT ?",
+        )
+        .bind(session_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut entries: Vec<ConversationEntry> = rows.into_iter().map(entry_from_row).collect();
+        entries.reverse();
+        Ok(entries)
+    }
+}
+
+fn speaker_label(speaker: &Speaker) -> &'static str {
+    match speaker {
+        Speaker::Pilot => "PILOT",
+        Speaker::Atc => "ATC",
+    }
+}
+
+fn entry_from_row(row: sqlx::sqlite::SqliteRow) -> ConversationEntry {
+    let speaker: String = row.get("speaker");
+    ConversationEntry {
+        speaker: if speaker == "PILOT" {
+            Speaker::Pilot
+        } else {
+            Speaker::Atc
+        },
+        message: row.get("message"),
+        timestamp: row.get("timestamp"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// A fresh on-disk path per test so concurrent test runs don't collide.
+    fn temp_db_path() -> std::path::PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("stratus_storage_test_{}_{}.db", std::process::id(), n))
+    }
+
+    #[tokio::test]
+    async fn round_trips_conversation_history_in_order() {
+        let path = temp_db_path();
+        let storage = Storage::open(&path).await.unwrap();
+        let session_id = storage.start_session("N12345", "C172").await.unwrap();
+
+        let pilot_entry = ConversationEntry {
+            speaker: Speaker::Pilot,
+            message: "tower, ready for departure".to_string(),
+            timestamp: 100,
+        };
+        let atc_entry = ConversationEntry {
+            speaker: Speaker::Atc,
+            message: "N12345, cleared for takeoff".to_string(),
+            timestamp: 101,
+        };
+
+        storage
+            .record_entry(session_id, &pilot_entry, Some(&Telemetry::default()))
+            .await
+            .unwrap();
+        storage.record_entry(session_id, &atc_entry, None).await.unwrap();
+
+        let transcript = storage.load_transcript(session_id).await.unwrap();
+        assert_eq!(transcript.len(), 2);
+        assert_eq!(transcript[0].speaker, Speaker::Pilot);
+        assert_eq!(transcript[0].message, pilot_entry.message);
+        assert_eq!(transcript[1].speaker, Speaker::Atc);
+        assert_eq!(transcript[1].message, atc_entry.message);
+
+        let recent = storage.recent_entries(session_id, 1).await.unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].message, atc_entry.message);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
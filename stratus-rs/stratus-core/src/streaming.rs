@@ -3,12 +3,12 @@
 //! Provides streaming LLM responses for low-latency ATC responses.
 //! Tokens are streamed and can be sent to TTS as they arrive.
 
-use futures_util::StreamExt;
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::time::{Duration, Instant};
+use std::time::Instant;
 use thiserror::Error;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::ollama::{OllamaClient, OllamaError};
 
 #[derive(Error, Debug)]
 pub enum StreamError {
@@ -20,6 +20,8 @@ pub enum StreamError {
     ParseError(#[from] serde_json::Error),
     #[error("Stream closed unexpectedly")]
     StreamClosed,
+    #[error("Ollama error: {0}")]
+    OllamaError(#[from] OllamaError),
 }
 
 /// A chunk of streamed response
@@ -28,35 +30,32 @@ pub struct StreamChunk {
     pub text: String,
     pub is_final: bool,
     pub latency_ms: u64,
+    /// Set on the final chunk of a stream that was cut short by
+    /// `StreamHandle::interrupt()` (barge-in), so the caller knows `text`
+    /// is a truncated transmission rather than a complete one.
+    pub interrupted: bool,
 }
 
-/// Response from Ollama streaming endpoint (each line)
-#[derive(Debug, Deserialize)]
-struct StreamLine {
-    response: String,
-    done: bool,
-}
-
-/// Request body for Ollama generate endpoint
-#[derive(Debug, Serialize)]
-struct GenerateRequest {
-    model: String,
-    prompt: String,
-    stream: bool,
-    options: GenerateOptions,
+/// Handle to an in-flight `generate_stream` call, returned alongside its
+/// receiver. Call `interrupt()` when the pilot keys the mic mid-transmission
+/// ("barge-in") - the spawned stream task stops forwarding new tokens,
+/// emits one final `StreamChunk` with `interrupted: true` carrying whatever
+/// was buffered so far, and drops the underlying Ollama response.
+#[derive(Clone)]
+pub struct StreamHandle {
+    cancel: CancellationToken,
 }
 
-#[derive(Debug, Serialize)]
-struct GenerateOptions {
-    temperature: f32,
-    num_predict: i32,
+impl StreamHandle {
+    /// Cancel the in-flight stream
+    pub fn interrupt(&self) {
+        self.cancel.cancel();
+    }
 }
 
 /// Streaming Ollama client for low-latency ATC responses
 pub struct StreamingOllama {
-    client: Client,
-    base_url: String,
-    model: String,
+    client: OllamaClient,
     min_chunk_chars: usize,
     max_chunk_chars: usize,
 }
@@ -65,9 +64,7 @@ impl StreamingOllama {
     /// Create a new streaming Ollama client
     pub fn new(model: impl Into<String>) -> Self {
         Self {
-            client: Client::new(),
-            base_url: "http://localhost:11434".to_string(),
-            model: model.into(),
+            client: OllamaClient::new(model),
             min_chunk_chars: 20,
             max_chunk_chars: 100,
         }
@@ -75,7 +72,7 @@ impl StreamingOllama {
 
     /// Set custom Ollama URL
     pub fn with_url(mut self, url: impl Into<String>) -> Self {
-        self.base_url = url.into();
+        self.client = self.client.with_url(url);
         self
     }
 
@@ -88,111 +85,37 @@ impl StreamingOllama {
 
     /// Check if Ollama is available
     pub async fn is_available(&self) -> bool {
-        self.client
-            .get(format!("{}/api/tags", self.base_url))
-            .timeout(Duration::from_secs(2))
-            .send()
-            .await
-            .is_ok()
+        self.client.is_available().await
     }
 
     /// Generate a streaming response
     ///
-    /// Returns a channel receiver that yields StreamChunk as they arrive.
-    /// Chunks are buffered until phrase boundaries (punctuation) or max size.
+    /// Returns a `StreamHandle` (call `.interrupt()` on pilot barge-in) and
+    /// a channel receiver that yields `StreamChunk`s as they arrive,
+    /// buffered until phrase boundaries (punctuation) or max size. Built on
+    /// top of `OllamaClient::generate_stream`, so a mid-stream Ollama error
+    /// closes the channel via `StreamError::OllamaError` instead of hanging.
+    #[tracing::instrument(skip(self, prompt), fields(model = %self.client.model(), prompt_len = prompt.len()))]
     pub async fn generate_stream(
         &self,
         prompt: &str,
-    ) -> Result<mpsc::Receiver<StreamChunk>, StreamError> {
-        let (tx, rx) = mpsc::channel(32);
+    ) -> Result<(StreamHandle, mpsc::Receiver<Result<StreamChunk, StreamError>>), StreamError> {
+        let tokens = self.client.generate_stream(prompt).await?;
 
-        let request = GenerateRequest {
-            model: self.model.clone(),
-            prompt: prompt.to_string(),
-            stream: true,
-            options: GenerateOptions {
-                temperature: 0.7,
-                num_predict: 256,
-            },
+        let (tx, rx) = mpsc::channel(32);
+        let cancel = CancellationToken::new();
+        let handle = StreamHandle {
+            cancel: cancel.clone(),
         };
 
-        let response = self
-            .client
-            .post(format!("{}/api/generate", self.base_url))
-            .json(&request)
-            .timeout(Duration::from_secs(30))
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(StreamError::NotAvailable);
-        }
-
         let min_chars = self.min_chunk_chars;
         let max_chars = self.max_chunk_chars;
-        let start_time = Instant::now();
-
-        // Spawn task to process stream
-        tokio::spawn(async move {
-            let mut buffer = String::new();
-            let mut stream = response.bytes_stream();
-
-            while let Some(chunk_result) = stream.next().await {
-                let bytes = match chunk_result {
-                    Ok(b) => b,
-                    Err(_) => break,
-                };
-
-                // Parse each line in the chunk
-                for line in String::from_utf8_lossy(&bytes).lines() {
-                    if line.is_empty() {
-                        continue;
-                    }
-
-                    let parsed: StreamLine = match serde_json::from_str(line) {
-                        Ok(p) => p,
-                        Err(_) => continue,
-                    };
-
-                    buffer.push_str(&parsed.response);
-
-                    // Check if we should emit a chunk
-                    let should_emit = parsed.done
-                        || buffer.len() >= max_chars
-                        || (buffer.len() >= min_chars && has_phrase_boundary(&buffer));
-
-                    if should_emit && !buffer.is_empty() {
-                        let chunk = StreamChunk {
-                            text: buffer.trim().to_string(),
-                            is_final: parsed.done,
-                            latency_ms: start_time.elapsed().as_millis() as u64,
-                        };
-                        buffer.clear();
-
-                        if tx.send(chunk).await.is_err() {
-                            break;
-                        }
-                    }
-
-                    if parsed.done {
-                        break;
-                    }
-                }
-            }
+        let model = self.client.model().to_string();
 
-            // Send any remaining buffer
-            if !buffer.is_empty() {
-                let _ = tx
-                    .send(StreamChunk {
-                        text: buffer.trim().to_string(),
-                        is_final: true,
-                        latency_ms: start_time.elapsed().as_millis() as u64,
-                    })
-                    .await;
-            }
-        });
+        // Spawn task to process the raw token stream
+        tokio::spawn(run_stream_loop(tokens, tx, cancel, min_chars, max_chars, model));
 
-        Ok(rx)
+        Ok((handle, rx))
     }
 
     /// Generate with a callback for each chunk (convenience method)
@@ -204,10 +127,11 @@ impl StreamingOllama {
     where
         F: FnMut(StreamChunk) + Send + 'static,
     {
-        let mut rx = self.generate_stream(prompt).await?;
+        let (_handle, mut rx) = self.generate_stream(prompt).await?;
         let mut full_response = String::new();
 
-        while let Some(chunk) = rx.recv().await {
+        while let Some(result) = rx.recv().await {
+            let chunk = result?;
             full_response.push_str(&chunk.text);
             full_response.push(' ');
             on_chunk(chunk);
@@ -217,6 +141,122 @@ impl StreamingOllama {
     }
 }
 
+/// Buffer raw tokens from `tokens` into phrase/size-bounded `StreamChunk`s
+/// on `tx`, until the stream ends, errors, or `cancel` fires (barge-in).
+/// Pulled out of `StreamingOllama::generate_stream` as a standalone
+/// function, independent of `OllamaClient`, so the buffering and
+/// interruption behavior can be driven with a plain `mpsc` channel in
+/// tests instead of a live Ollama server.
+///
+/// Time-to-first-chunk and inter-chunk gap (the latency numbers that
+/// actually matter for ATC responsiveness) are recorded here via
+/// `crate::otel`; they only leave the process once the binary has called
+/// `otel::init` with the `otel` feature enabled, which installs the
+/// `MeterProvider` these calls resolve against.
+async fn run_stream_loop(
+    mut tokens: mpsc::Receiver<Result<String, OllamaError>>,
+    tx: mpsc::Sender<Result<StreamChunk, StreamError>>,
+    cancel: CancellationToken,
+    min_chars: usize,
+    max_chars: usize,
+    model: String,
+) {
+    let start_time = Instant::now();
+    let mut buffer = String::new();
+    let mut chunk_count: u64 = 0;
+    let mut last_emit = start_time;
+    let mut first_chunk_recorded = false;
+
+    loop {
+        let result = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => {
+                tracing::debug!("stream interrupted by barge-in");
+                let _ = tx
+                    .send(Ok(StreamChunk {
+                        text: buffer.trim().to_string(),
+                        is_final: true,
+                        latency_ms: start_time.elapsed().as_millis() as u64,
+                        interrupted: true,
+                    }))
+                    .await;
+                break;
+            }
+            result = tokens.recv() => result,
+        };
+
+        let Some(result) = result else { break };
+
+        let token = match result {
+            Ok(token) => token,
+            Err(e) => {
+                let _ = tx.send(Err(StreamError::OllamaError(e))).await;
+                break;
+            }
+        };
+
+        buffer.push_str(&token);
+
+        let at_max_chars = buffer.len() >= max_chars;
+        let at_phrase_boundary = buffer.len() >= min_chars && has_phrase_boundary(&buffer);
+        let should_emit = at_max_chars || at_phrase_boundary;
+
+        if should_emit && !buffer.is_empty() {
+            let now = Instant::now();
+            if first_chunk_recorded {
+                crate::otel::record_latency_ms(
+                    "ollama_stream_inter_chunk_gap_ms",
+                    &model,
+                    now.duration_since(last_emit).as_millis() as u64,
+                );
+            } else {
+                crate::otel::record_latency_ms(
+                    "ollama_stream_ttfc_ms",
+                    &model,
+                    now.duration_since(start_time).as_millis() as u64,
+                );
+                first_chunk_recorded = true;
+            }
+            last_emit = now;
+            chunk_count += 1;
+
+            tracing::debug!(
+                emit_reason = if at_phrase_boundary { "phrase_boundary" } else { "max_chunk_chars" },
+                chunk_chars = buffer.len(),
+                "emitting stream chunk"
+            );
+
+            let chunk = StreamChunk {
+                text: buffer.trim().to_string(),
+                is_final: false,
+                latency_ms: start_time.elapsed().as_millis() as u64,
+                interrupted: false,
+            };
+            buffer.clear();
+
+            if tx.send(Ok(chunk)).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    // Send any remaining buffer as the final chunk, unless we just sent an
+    // interrupted one above
+    if !buffer.is_empty() && !cancel.is_cancelled() {
+        chunk_count += 1;
+        let _ = tx
+            .send(Ok(StreamChunk {
+                text: buffer.trim().to_string(),
+                is_final: true,
+                latency_ms: start_time.elapsed().as_millis() as u64,
+                interrupted: false,
+            }))
+            .await;
+    }
+
+    crate::otel::record_counter("ollama_stream_chunks_total", &model, chunk_count);
+}
+
 /// Check if buffer ends with a phrase boundary
 fn has_phrase_boundary(s: &str) -> bool {
     s.ends_with('.')
@@ -244,4 +284,69 @@ mod tests {
         // This will fail if Ollama isn't running, which is fine for unit tests
         let _ = client.is_available().await;
     }
+
+    #[tokio::test]
+    async fn emits_a_chunk_at_each_phrase_boundary() {
+        let (token_tx, token_rx) = mpsc::channel(8);
+        let (chunk_tx, mut chunk_rx) = mpsc::channel(8);
+        let cancel = CancellationToken::new();
+
+        tokio::spawn(run_stream_loop(
+            token_rx,
+            chunk_tx,
+            cancel,
+            5,
+            100,
+            "test-model".to_string(),
+        ));
+
+        token_tx.send(Ok("Roger, ".to_string())).await.unwrap();
+        token_tx.send(Ok("cleared for takeoff.".to_string())).await.unwrap();
+        drop(token_tx);
+
+        let first = chunk_rx.recv().await.unwrap().unwrap();
+        assert_eq!(first.text, "Roger,");
+        assert!(!first.is_final);
+        assert!(!first.interrupted);
+
+        let second = chunk_rx.recv().await.unwrap().unwrap();
+        assert_eq!(second.text, "cleared for takeoff.");
+        assert!(second.is_final);
+        assert!(!second.interrupted);
+
+        assert!(chunk_rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn interrupt_emits_one_final_truncated_chunk() {
+        let (token_tx, token_rx) = mpsc::channel(8);
+        let (chunk_tx, mut chunk_rx) = mpsc::channel(8);
+        let cancel = CancellationToken::new();
+
+        tokio::spawn(run_stream_loop(
+            token_rx,
+            chunk_tx,
+            cancel.clone(),
+            5,
+            100,
+            "test-model".to_string(),
+        ));
+
+        token_tx.send(Ok("N12345, tur".to_string())).await.unwrap();
+        // Let the spawned task actually consume the token into its buffer
+        // before interrupting it, so the assertion below isn't racing
+        // against the task's own scheduling.
+        tokio::task::yield_now().await;
+        tokio::task::yield_now().await;
+        cancel.cancel();
+
+        let chunk = chunk_rx.recv().await.unwrap().unwrap();
+        assert!(chunk.is_final);
+        assert!(chunk.interrupted);
+        assert_eq!(chunk.text, "N12345, tur");
+
+        // No further chunks (in particular, no duplicate non-interrupted
+        // final chunk for the same buffered text) once interrupted.
+        assert!(chunk_rx.recv().await.is_none());
+    }
 }
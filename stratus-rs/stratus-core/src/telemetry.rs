@@ -17,6 +17,8 @@ pub enum TelemetryError {
     ParseError(#[from] serde_json::Error),
     #[error("File watcher error: {0}")]
     WatchError(#[from] notify::Error),
+    #[error("Transport error: {0}")]
+    TransportError(String),
 }
 
 /// Aircraft telemetry from X-Plane
@@ -113,7 +115,7 @@ impl TelemetryWatcher {
     }
 
     /// Get the platform-specific data directory
-    fn get_data_dir() -> PathBuf {
+    pub(crate) fn get_data_dir() -> PathBuf {
         #[cfg(target_os = "linux")]
         {
             dirs::data_local_dir()
@@ -0,0 +1,162 @@
+//! Transport abstraction for telemetry ingestion and command dispatch
+//!
+//! `TelemetryWatcher` talks to X-Plane by polling a file on disk, which
+//! means the GUI has to run on the same machine as the sim and pays a
+//! filesystem-latency tax per poll. These traits pull that plumbing behind
+//! an interface so a single engine can instead consume telemetry from (and
+//! publish commands to) a message bus. `FileTransport` wraps the existing
+//! file-based behavior; the `nats` feature adds `NatsTransport` alongside
+//! it without removing the file path.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::telemetry::{Telemetry, TelemetryError, TelemetryWatcher};
+
+/// A command relayed back to the simulator (e.g. a frequency change issued
+/// by ATC), keyed by aircraft callsign so one transport can serve several
+/// aircraft at once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Command {
+    pub callsign: String,
+    pub kind: String,
+    pub payload: serde_json::Value,
+}
+
+/// Source of telemetry updates, polled by the GUI/engine loop
+#[async_trait]
+pub trait TelemetrySource: Send + Sync {
+    /// Check for a new telemetry update without blocking
+    async fn poll(&mut self) -> Option<Result<Telemetry, TelemetryError>>;
+
+    /// Read the current/last-known telemetry
+    async fn read_telemetry(&self) -> Result<Telemetry, TelemetryError>;
+}
+
+/// Sink for commands issued back to the simulator
+#[async_trait]
+pub trait CommandSink: Send + Sync {
+    async fn send_command(&self, command: &Command) -> Result<(), TelemetryError>;
+}
+
+/// File-based transport - the original `TelemetryWatcher`/`.jsonl` pair,
+/// now implementing the transport traits instead of being the only option.
+pub struct FileTransport {
+    watcher: TelemetryWatcher,
+    commands_path: std::path::PathBuf,
+}
+
+impl FileTransport {
+    pub fn new() -> Result<Self, TelemetryError> {
+        let watcher = TelemetryWatcher::new()?;
+        let commands_path = TelemetryWatcher::get_data_dir().join("stratus_commands.jsonl");
+        Ok(Self {
+            watcher,
+            commands_path,
+        })
+    }
+}
+
+#[async_trait]
+impl TelemetrySource for FileTransport {
+    async fn poll(&mut self) -> Option<Result<Telemetry, TelemetryError>> {
+        self.watcher.poll()
+    }
+
+    async fn read_telemetry(&self) -> Result<Telemetry, TelemetryError> {
+        self.watcher.read_telemetry()
+    }
+}
+
+#[async_trait]
+impl CommandSink for FileTransport {
+    async fn send_command(&self, command: &Command) -> Result<(), TelemetryError> {
+        use tokio::io::AsyncWriteExt;
+
+        let line = format!("{}\n", serde_json::to_string(command)?);
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.commands_path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// NATS-backed transport, gated behind the `nats` feature so a build that
+/// only ever talks to a local sim doesn't pull in the client/JetStream
+/// dependencies.
+#[cfg(feature = "nats")]
+pub mod nats_transport {
+    use super::*;
+    use async_nats::jetstream::{self, stream::Config as StreamConfig};
+    use futures_util::{FutureExt, StreamExt};
+
+    /// Telemetry is consumed from `stratus.telemetry.<callsign>`; commands
+    /// are published to `stratus.commands.<callsign>` through a JetStream
+    /// stream so they survive a brief sim disconnect and can be replayed.
+    pub struct NatsTransport {
+        jetstream: jetstream::Context,
+        subscriber: async_nats::Subscriber,
+        latest: Option<Telemetry>,
+    }
+
+    impl NatsTransport {
+        pub async fn connect(url: &str, callsign: &str) -> anyhow::Result<Self> {
+            let client = async_nats::connect(url).await?;
+            let jetstream = jetstream::new(client.clone());
+            jetstream
+                .get_or_create_stream(StreamConfig {
+                    name: "STRATUS_COMMANDS".to_string(),
+                    subjects: vec!["stratus.commands.*".to_string()],
+                    ..Default::default()
+                })
+                .await?;
+
+            let subscriber = client
+                .subscribe(format!("stratus.telemetry.{callsign}"))
+                .await?;
+
+            Ok(Self {
+                jetstream,
+                subscriber,
+                latest: None,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl TelemetrySource for NatsTransport {
+        async fn poll(&mut self) -> Option<Result<Telemetry, TelemetryError>> {
+            let message = self.subscriber.next().now_or_never().flatten()?;
+            let result =
+                serde_json::from_slice::<Telemetry>(&message.payload).map_err(TelemetryError::from);
+            if let Ok(telemetry) = &result {
+                self.latest = Some(telemetry.clone());
+            }
+            Some(result)
+        }
+
+        async fn read_telemetry(&self) -> Result<Telemetry, TelemetryError> {
+            self.latest.clone().ok_or_else(|| {
+                TelemetryError::TransportError("no telemetry received yet".to_string())
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CommandSink for NatsTransport {
+        async fn send_command(&self, command: &Command) -> Result<(), TelemetryError> {
+            let payload = serde_json::to_vec(command)?;
+            self.jetstream
+                .publish(format!("stratus.commands.{}", command.callsign), payload.into())
+                .await
+                .map_err(|e| TelemetryError::TransportError(e.to_string()))?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "nats")]
+pub use nats_transport::NatsTransport;
@@ -172,6 +172,7 @@ impl WarmupService {
 }
 
 /// Send a minimal warmup prompt to keep model loaded
+#[tracing::instrument(skip(client, config), fields(model = %config.model))]
 async fn send_heartbeat(client: &Client, config: &WarmupConfig) -> u64 {
     let start = Instant::now();
 
@@ -206,6 +207,8 @@ async fn send_heartbeat(client: &Client, config: &WarmupConfig) -> u64 {
         }
     }
 
+    crate::otel::record_latency_ms("warmup_heartbeat_latency_ms", &config.model, latency);
+
     latency
 }
 
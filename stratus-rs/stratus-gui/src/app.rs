@@ -5,10 +5,22 @@
 use iced::widget::{
     column, container, horizontal_space, row, scrollable, text, text_input, Column,
 };
-use iced::{time, Element, Length, Subscription, Task, Theme};
+use futures_util::SinkExt;
+use iced::{stream, time, Element, Length, Subscription, Task, Theme};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
-use stratus_core::Telemetry;
+use stratus_core::{Telemetry, WarmupConfig, WarmupService, WarmupStats};
+
+use crate::comlink::{CommLogEntry, ComLinkState};
+use crate::voice::VoiceState;
+
+/// Heartbeat latency above which the model is considered cold and still
+/// loading into memory, rather than just responding normally.
+const WARMING_THRESHOLD_MS: u64 = 1000;
+
+/// Port the embedded ComLink web server (tablet/VR client) listens on.
+const COMLINK_PORT: u16 = 8088;
 
 /// Main application state
 pub struct StratusApp {
@@ -21,6 +33,10 @@ pub struct StratusApp {
     connected: bool,
     last_telemetry_time: std::time::Instant,
     ollama_status: OllamaStatus,
+    warmup: Arc<WarmupService>,
+
+    // ComLink (embedded web server for tablet/VR clients)
+    comlink: ComLinkState,
 
     // Paths
     data_dir: PathBuf,
@@ -38,6 +54,9 @@ pub enum OllamaStatus {
     Unknown,
     Connected,
     Disconnected,
+    /// Model is cold and still loading into memory (first inference after
+    /// idle takes 5-15s), as inferred from a slow warmup heartbeat.
+    Warming { latency_ms: u64 },
 }
 
 /// Messages that can be sent to the application
@@ -50,6 +69,9 @@ pub enum Message {
     // Background events
     TelemetryUpdated(Result<Telemetry, String>),
     OllamaStatusChanged(bool),
+    WarmupStatsUpdated(WarmupStats),
+    PilotMessageWarmed(String),
+    ComLinkServerStopped(Result<(), String>),
 
     // System
     Tick,
@@ -61,6 +83,11 @@ impl StratusApp {
     pub fn new() -> (Self, Task<Message>) {
         let data_dir = Self::get_data_dir();
 
+        let warmup = Arc::new(WarmupService::new(WarmupConfig::default()));
+        warmup.start();
+
+        let comlink = ComLinkState::new();
+
         let app = Self {
             input_text: String::new(),
             comm_log: vec![CommEntry {
@@ -71,13 +98,19 @@ impl StratusApp {
             connected: false,
             last_telemetry_time: std::time::Instant::now(),
             ollama_status: OllamaStatus::Unknown,
+            warmup: warmup.clone(),
+            comlink: comlink.clone(),
             data_dir,
         };
 
         // Initial tasks
         let check_ollama = Task::perform(check_ollama_available(), Message::OllamaStatusChanged);
+        let start_comlink = Task::perform(
+            start_comlink_server(comlink, warmup),
+            Message::ComLinkServerStopped,
+        );
 
-        (app, check_ollama)
+        (app, Task::batch([check_ollama, start_comlink]))
     }
 
     fn get_data_dir() -> PathBuf {
@@ -100,19 +133,51 @@ impl StratusApp {
                         speaker: "PILOT".into(),
                         message: pilot_msg.clone(),
                     });
+                    self.comlink.publish_comm_log(CommLogEntry {
+                        speaker: "PILOT".into(),
+                        message: pilot_msg.clone(),
+                    });
                     self.input_text.clear();
 
-                    // TODO: Send to ATC engine (Phase 3)
-                    self.comm_log.push(CommEntry {
-                        speaker: "ATC".into(),
-                        message: format!("Roger, {}", pilot_msg),
-                    });
+                    // Make sure the model is hot before we need a response,
+                    // and pause heartbeats so they don't collide with it.
+                    self.warmup.pause();
+                    let warmup = self.warmup.clone();
+                    Task::perform(
+                        async move {
+                            warmup.force_warmup().await;
+                            pilot_msg
+                        },
+                        Message::PilotMessageWarmed,
+                    )
+                } else {
+                    Task::none()
+                }
+            }
+            Message::PilotMessageWarmed(pilot_msg) => {
+                // TODO: Send to ATC engine (Phase 3)
+                let message = format!("Roger, {}", pilot_msg);
+                self.comm_log.push(CommEntry {
+                    speaker: "ATC".into(),
+                    message: message.clone(),
+                });
+                self.comlink.publish_comm_log(CommLogEntry {
+                    speaker: "ATC".into(),
+                    message,
+                });
+                self.warmup.resume();
+                Task::none()
+            }
+            Message::ComLinkServerStopped(result) => {
+                if let Err(e) = result {
+                    tracing::error!("ComLink server stopped unexpectedly: {e}");
                 }
                 Task::none()
             }
             Message::TelemetryUpdated(result) => {
                 match result {
                     Ok(telemetry) => {
+                        self.comlink.publish_telemetry(telemetry.clone());
                         self.telemetry = telemetry;
                         self.connected = true;
                         self.last_telemetry_time = std::time::Instant::now();
@@ -134,6 +199,16 @@ impl StratusApp {
                 };
                 Task::none()
             }
+            Message::WarmupStatsUpdated(stats) => {
+                if stats.heartbeat_count > 0 && stats.last_latency_ms > WARMING_THRESHOLD_MS {
+                    self.ollama_status = OllamaStatus::Warming {
+                        latency_ms: stats.last_latency_ms,
+                    };
+                } else if self.ollama_status != OllamaStatus::Disconnected {
+                    self.ollama_status = OllamaStatus::Connected;
+                }
+                Task::none()
+            }
             Message::Tick => {
                 // Read telemetry file
                 let path = self.data_dir.join("stratus_telemetry.json");
@@ -172,6 +247,9 @@ impl StratusApp {
             OllamaStatus::Connected => text("🧠 Ollama Ready").color([0.3, 0.9, 0.3]),
             OllamaStatus::Disconnected => text("⚠ Ollama Offline").color([0.9, 0.6, 0.3]),
             OllamaStatus::Unknown => text("? Checking Ollama...").color([0.6, 0.6, 0.6]),
+            OllamaStatus::Warming { latency_ms } => {
+                text(format!("🧠 Model warming... {}ms", latency_ms)).color([0.9, 0.8, 0.3])
+            }
         };
 
         row![
@@ -277,12 +355,36 @@ impl StratusApp {
         // Check Ollama every 10 seconds
         let ollama_check = time::every(Duration::from_secs(10)).map(|_| Message::CheckOllama);
 
-        Subscription::batch([telemetry_tick, ollama_check])
+        // Stream warmup heartbeat stats as they're published
+        let warmup_stats = warmup_stats_subscription(self.warmup.clone());
+
+        Subscription::batch([telemetry_tick, ollama_check, warmup_stats])
     }
 }
 
+/// Subscription that forwards every update from the warmup service's
+/// `watch::Receiver` as a `Message::WarmupStatsUpdated`.
+fn warmup_stats_subscription(warmup: Arc<WarmupService>) -> Subscription<Message> {
+    Subscription::run_with_id(
+        "warmup-stats",
+        stream::channel(16, move |mut output| async move {
+            let mut rx = warmup.stats();
+            loop {
+                if rx.changed().await.is_err() {
+                    break;
+                }
+                let stats = rx.borrow().clone();
+                if output.send(Message::WarmupStatsUpdated(stats)).await.is_err() {
+                    break;
+                }
+            }
+        }),
+    )
+}
+
 // Async helper functions
 
+#[tracing::instrument(skip(path))]
 async fn read_telemetry_file(path: PathBuf) -> Result<Telemetry, String> {
     let content = tokio::fs::read_to_string(&path)
         .await
@@ -291,6 +393,20 @@ async fn read_telemetry_file(path: PathBuf) -> Result<Telemetry, String> {
     serde_json::from_str(&content).map_err(|e| e.to_string())
 }
 
+/// Start the embedded ComLink web server (tablet/VR client) in the
+/// background. Runs for the lifetime of the app - the returned `Task`
+/// only resolves (with an error) if the server stops unexpectedly.
+async fn start_comlink_server(state: ComLinkState, warmup: Arc<WarmupService>) -> Result<(), String> {
+    let voice_state = VoiceState::new(warmup).map_err(|e| e.to_string())?;
+
+    #[cfg(feature = "http3")]
+    let result = crate::comlink::start_server(COMLINK_PORT, state, voice_state, None).await;
+    #[cfg(not(feature = "http3"))]
+    let result = crate::comlink::start_server(COMLINK_PORT, state, voice_state).await;
+
+    result.map_err(|e| e.to_string())
+}
+
 async fn check_ollama_available() -> bool {
     reqwest::get("http://localhost:11434/api/tags")
         .await
@@ -1,57 +1,183 @@
 //! ComLink - Embedded Web Server
 //!
-//! Serves the ComLink web interface for tablet/VR access.
+//! Serves the ComLink web interface for tablet/VR access. Telemetry and
+//! comm-log updates are pushed to connected clients over `/ws` so a tablet
+//! or VR headset gets live updates instead of polling a snapshot endpoint.
 
-use axum::{routing::get, Router};
+use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
+    extract::State,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
 use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use tower_http::services::ServeDir;
 
-/// Create the Axum router for ComLink
-pub fn create_router() -> Router {
-    Router::new()
-        .route("/", get(index_handler))
-        .route("/api/status", get(status_handler))
-        .route("/api/telemetry", get(telemetry_handler))
+use stratus_core::Telemetry;
+
+use crate::voice::{voice_router, VoiceState};
+
+/// Directory the static ComLink web assets are served from, resolved at
+/// compile time against this crate's manifest directory - a bare relative
+/// path would 404 unless the binary happened to be launched from
+/// `stratus-rs/`.
+const COMLINK_ASSETS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/comlink");
+
+/// A single entry in the communications log, as pushed to ComLink clients
+#[derive(Debug, Clone, Serialize)]
+pub struct CommLogEntry {
+    pub speaker: String,
+    pub message: String,
+}
+
+/// Events pushed to connected ComLink clients over `/ws`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ComLinkEvent {
+    Telemetry(Telemetry),
+    CommLog(CommLogEntry),
 }
 
-async fn index_handler() -> &'static str {
-    r#"<!DOCTYPE html>
-<html>
-<head>
-    <title>Stratus ComLink</title>
-    <style>
-        body { 
-            background: #1a1a2e; 
-            color: #eee; 
-            font-family: sans-serif;
-            padding: 20px;
+/// Shared application state for the ComLink server
+///
+/// Cloning is cheap - the `Arc`/`Sender` inside are shared across every
+/// connected tablet/VR client, so publishing an update once fans it out to
+/// all of them without re-reading a file per client.
+#[derive(Clone)]
+pub struct ComLinkState {
+    telemetry: Arc<RwLock<Telemetry>>,
+    events: broadcast::Sender<ComLinkEvent>,
+}
+
+impl ComLinkState {
+    pub fn new() -> Self {
+        let (events, _rx) = broadcast::channel(64);
+        Self {
+            telemetry: Arc::new(RwLock::new(Telemetry::default())),
+            events,
         }
-        h1 { color: #4a9eff; }
-    </style>
-</head>
-<body>
-    <h1>Stratus ComLink</h1>
-    <p>Web interface coming soon...</p>
-</body>
-</html>"#
+    }
+
+    /// Publish a fresh telemetry snapshot to every connected client
+    pub fn publish_telemetry(&self, telemetry: Telemetry) {
+        *self.telemetry.write().unwrap() = telemetry.clone();
+        let _ = self.events.send(ComLinkEvent::Telemetry(telemetry));
+    }
+
+    /// Publish a new comm-log entry to every connected client
+    pub fn publish_comm_log(&self, entry: CommLogEntry) {
+        let _ = self.events.send(ComLinkEvent::CommLog(entry));
+    }
+}
+
+impl Default for ComLinkState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create the Axum router for ComLink, with the WHIP/WHEP voice endpoints
+/// mounted alongside the text/telemetry ones.
+pub fn create_router(state: ComLinkState, voice_state: VoiceState) -> Router {
+    Router::new()
+        .route("/api/status", get(status_handler))
+        .route("/api/telemetry", get(telemetry_handler))
+        .route("/ws", get(ws_handler))
+        .with_state(state)
+        .merge(voice_router().with_state(voice_state))
+        .nest_service("/static", ServeDir::new(COMLINK_ASSETS_DIR))
+        .fallback_service(ServeDir::new(COMLINK_ASSETS_DIR))
 }
 
 async fn status_handler() -> &'static str {
     r#"{"status": "ok", "connected": true}"#
 }
 
-async fn telemetry_handler() -> &'static str {
-    r#"{"altitude": 0, "heading": 0, "speed": 0}"#
+async fn telemetry_handler(State(state): State<ComLinkState>) -> impl IntoResponse {
+    let telemetry = state.telemetry.read().unwrap().clone();
+    Json(telemetry)
+}
+
+/// Upgrade the connection to a WebSocket and start streaming updates
+async fn ws_handler(ws: WebSocketUpgrade, State(state): State<ComLinkState>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+async fn handle_socket(mut socket: WebSocket, state: ComLinkState) {
+    // Send the current snapshot right away so a newly connected client
+    // doesn't have to wait for the next telemetry update to render anything.
+    let snapshot = state.telemetry.read().unwrap().clone();
+    if let Ok(json) = serde_json::to_string(&ComLinkEvent::Telemetry(snapshot)) {
+        if socket.send(Message::Text(json)).await.is_err() {
+            return;
+        }
+    }
+
+    let mut events = state.events.subscribe();
+    while let Ok(event) = events.recv().await {
+        let Ok(json) = serde_json::to_string(&event) else {
+            continue;
+        };
+        if socket.send(Message::Text(json)).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Configuration for the opt-in HTTP/3 (QUIC) listener, gated behind the
+/// `http3` feature and run alongside (not instead of) the TCP/HTTP1 one.
+#[cfg(feature = "http3")]
+#[derive(Debug, Clone)]
+pub struct Http3Config {
+    pub port: u16,
+    pub cert_path: String,
+    pub key_path: String,
 }
 
-/// Start the ComLink web server
-pub async fn start_server(port: u16) -> anyhow::Result<()> {
-    let app = create_router();
+/// Start the ComLink web server. When built with the `http3` feature and
+/// `http3_config` is `Some`, also starts a QUIC listener on `http3_config.port`
+/// and advertises it to HTTP/1 clients via `Alt-Svc`.
+pub async fn start_server(
+    port: u16,
+    state: ComLinkState,
+    voice_state: VoiceState,
+    #[cfg(feature = "http3")] http3_config: Option<Http3Config>,
+) -> anyhow::Result<()> {
+    let mut app = create_router(state, voice_state);
+
+    #[cfg(feature = "http3")]
+    if let Some(config) = &http3_config {
+        app = app.layer(axum::middleware::from_fn_with_state(
+            config.port,
+            crate::http3::advertise_http3,
+        ));
+    }
+
     let addr = SocketAddr::from(([127, 0, 0, 1], port));
-    
     tracing::info!("ComLink server listening on http://{}", addr);
-    
     let listener = tokio::net::TcpListener::bind(addr).await?;
+
+    #[cfg(feature = "http3")]
+    if let Some(config) = http3_config {
+        let http3_addr = SocketAddr::from(([127, 0, 0, 1], config.port));
+        let http3_app = app.clone();
+        return tokio::try_join!(
+            async { axum::serve(listener, app).await.map_err(anyhow::Error::from) },
+            crate::http3::start_http3_server(
+                http3_app,
+                http3_addr,
+                &config.cert_path,
+                &config.key_path
+            ),
+        )
+        .map(|_| ());
+    }
+
     axum::serve(listener, app).await?;
-    
+
     Ok(())
 }
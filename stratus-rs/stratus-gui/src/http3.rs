@@ -0,0 +1,115 @@
+//! HTTP/3 (QUIC) transport for the embedded ComLink server
+//!
+//! ComLink chunks are streamed token-by-token from `StreamingOllama`, so a
+//! single dropped packet on HTTP/1 stalls the whole transmission thanks to
+//! head-of-line blocking. This adds an opt-in HTTP/3-over-QUIC listener,
+//! gated behind the `http3` feature, running alongside (not instead of)
+//! the existing TCP/HTTP1 listener in `comlink::start_server`. Both serve
+//! the same axum `Router` - the streaming chunk forwarder is untouched,
+//! only the transport underneath it changes.
+
+#![cfg(feature = "http3")]
+
+use axum::Router;
+use bytes::{Buf, Bytes, BytesMut};
+use http_body_util::BodyExt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Start the HTTP/3 listener. Requires a TLS certificate/key pair since
+/// QUIC mandates TLS 1.3; `cert_path`/`key_path` should point at the same
+/// certificate used (or usable) for the HTTP/1 listener.
+pub async fn start_http3_server(
+    router: Router,
+    addr: SocketAddr,
+    cert_path: &str,
+    key_path: &str,
+) -> anyhow::Result<()> {
+    let cert_chain = vec![rustls::Certificate(std::fs::read(cert_path)?)];
+    let key = rustls::PrivateKey(std::fs::read(key_path)?);
+
+    let mut tls_config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(cert_chain, key)?;
+    tls_config.alpn_protocols = vec![b"h3".to_vec()];
+
+    let server_config = quinn::ServerConfig::with_crypto(Arc::new(tls_config));
+    let endpoint = quinn::Endpoint::server(server_config, addr)?;
+
+    tracing::info!("ComLink HTTP/3 listener on {}", addr);
+
+    while let Some(connecting) = endpoint.accept().await {
+        let router = router.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(connecting, router).await {
+                tracing::warn!("HTTP/3 connection error: {e}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn handle_connection(connecting: quinn::Connecting, router: Router) -> anyhow::Result<()> {
+    let conn = connecting.await?;
+    let mut h3_conn = h3::server::Connection::new(h3_quinn::Connection::new(conn)).await?;
+
+    while let Some((req, stream)) = h3_conn.accept().await? {
+        let router = router.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_request(req, stream, router).await {
+                tracing::warn!("HTTP/3 request error: {e}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn handle_request<S>(
+    req: http::Request<()>,
+    mut stream: h3::server::RequestStream<S, Bytes>,
+    router: Router,
+) -> anyhow::Result<()>
+where
+    S: h3::quic::BidiStream<Bytes>,
+{
+    let mut request_body = BytesMut::new();
+    while let Some(mut chunk) = stream.recv_data().await? {
+        request_body.extend_from_slice(&chunk.copy_to_bytes(chunk.remaining()));
+    }
+    let request = req.map(|_| axum::body::Body::from(request_body.freeze()));
+    let response = router.oneshot(request).await?;
+
+    let (parts, mut body) = response.into_parts();
+    stream
+        .send_response(http::Response::from_parts(parts, ()))
+        .await?;
+
+    while let Some(frame) = body.frame().await {
+        if let Ok(data) = frame?.into_data() {
+            stream.send_data(data).await?;
+        }
+    }
+    stream.finish().await?;
+
+    Ok(())
+}
+
+/// Axum middleware that advertises the HTTP/3 endpoint via `Alt-Svc` on
+/// every HTTP/1 response, so clients know they can upgrade.
+pub async fn advertise_http3(
+    axum::extract::State(port): axum::extract::State<u16>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    use axum::http::{header, HeaderValue};
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&format!(r#"h3=":{port}"; ma=3600"#)) {
+        response.headers_mut().insert(header::ALT_SVC, value);
+    }
+    response
+}
@@ -4,17 +4,18 @@
 
 mod app;
 mod comlink;
+mod http3;
 mod theme;
+mod voice;
 
 use anyhow::Result;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use stratus_core::{otel, OtelConfig};
 
 fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
-        .with(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
+    // Initialize logging, with OTLP export layered on when the `otel`
+    // feature is enabled (a no-op otherwise) - this is also what installs
+    // the global `MeterProvider` that `otel::record_*` depend on.
+    otel::init(&OtelConfig::from_env())?;
 
     tracing::info!("Stratus ATC starting...");
 
@@ -28,5 +29,7 @@ fn main() -> Result<()> {
     .subscription(app::StratusApp::subscription)
     .run_with(app::StratusApp::new)?;
 
+    otel::shutdown();
+
     Ok(())
 }
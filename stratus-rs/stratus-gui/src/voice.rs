@@ -0,0 +1,283 @@
+//! Voice - WHIP/WHEP WebRTC audio streaming
+//!
+//! ATC is inherently voice-based, so ComLink also exposes a voice channel
+//! alongside its text comm log: synthesized ATC audio is published to
+//! browser/VR clients using WHEP (the WebRTC-HTTP Egress Protocol), and
+//! pilot push-to-talk audio flows back in over the reciprocal WHIP
+//! protocol. Both are signaled as a single SDP offer/answer POST, reusing
+//! the ComLink axum router; audio itself flows over the negotiated
+//! `RTCPeerConnection`, not through axum.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use webrtc::api::media_engine::{MediaEngine, MIME_TYPE_OPUS};
+use webrtc::api::{APIBuilder, API};
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::rtp_transceiver::rtp_codec::RTCRtpCodecCapability;
+use webrtc::track::track_local::track_local_static_sample::TrackLocalStaticSample;
+use webrtc::track::track_remote::TrackRemote;
+
+use stratus_core::WarmupService;
+
+/// A negotiated WHIP/WHEP session, keyed by the resource id handed back in
+/// the `Location` header. Kept alive here so the `RTCPeerConnection` isn't
+/// dropped (and torn down) the moment the offer/answer handler returns -
+/// ICE/DTLS, and therefore audio, only completes after the answer is sent.
+struct Session {
+    pc: Arc<RTCPeerConnection>,
+}
+
+/// Shared state for the voice subsystem
+#[derive(Clone)]
+pub struct VoiceState {
+    api: Arc<API>,
+    /// Opus track carrying synthesized ATC audio out to WHEP subscribers.
+    /// Feed it from the TTS pipeline with `atc_track.write_sample(...)`.
+    pub atc_track: Arc<TrackLocalStaticSample>,
+    /// Paused automatically while a WHIP ingest session is active, so pilot
+    /// transmissions don't contend with heartbeat prompts.
+    warmup: Arc<WarmupService>,
+    /// Number of WHIP ingest sessions currently connected.
+    ingest_sessions: Arc<Mutex<u32>>,
+    /// Live WHIP/WHEP sessions, keyed by resource id, so `DELETE` can look
+    /// up and close the matching `RTCPeerConnection`.
+    sessions: Arc<Mutex<HashMap<String, Session>>>,
+    next_resource_id: Arc<AtomicU64>,
+}
+
+impl VoiceState {
+    pub fn new(warmup: Arc<WarmupService>) -> anyhow::Result<Self> {
+        let mut media_engine = MediaEngine::default();
+        media_engine.register_default_codecs()?;
+        let api = APIBuilder::new().with_media_engine(media_engine).build();
+
+        let atc_track = Arc::new(TrackLocalStaticSample::new(
+            RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_OPUS.to_string(),
+                ..Default::default()
+            },
+            "atc-audio".to_string(),
+            "stratus-atc".to_string(),
+        ));
+
+        Ok(Self {
+            api: Arc::new(api),
+            atc_track,
+            warmup,
+            ingest_sessions: Arc::new(Mutex::new(0)),
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            next_resource_id: Arc::new(AtomicU64::new(1)),
+        })
+    }
+
+    async fn new_peer_connection(&self) -> anyhow::Result<Arc<RTCPeerConnection>> {
+        Ok(Arc::new(
+            self.api
+                .new_peer_connection(RTCConfiguration::default())
+                .await?,
+        ))
+    }
+
+    /// Store a negotiated connection under a freshly minted resource id.
+    async fn insert_session(&self, pc: Arc<RTCPeerConnection>) -> String {
+        let resource_id = self
+            .next_resource_id
+            .fetch_add(1, Ordering::Relaxed)
+            .to_string();
+        self.sessions
+            .lock()
+            .await
+            .insert(resource_id.clone(), Session { pc });
+        resource_id
+    }
+
+    /// Remove and close a session by resource id. Returns `true` if a
+    /// session was found (and closed).
+    async fn close_session(&self, resource_id: &str) -> bool {
+        let Some(session) = self.sessions.lock().await.remove(resource_id) else {
+            return false;
+        };
+        if let Err(e) = session.pc.close().await {
+            tracing::warn!("error closing WHIP/WHEP peer connection: {e}");
+        }
+        true
+    }
+}
+
+/// Router for the `/whep` (ATC audio out) and `/whip` (pilot audio in)
+/// endpoints, mounted alongside the rest of the ComLink router.
+pub fn voice_router() -> Router<VoiceState> {
+    Router::new()
+        .route("/whep", post(whep_offer))
+        .route("/whep/:resource_id", axum::routing::delete(whep_delete))
+        .route("/whip", post(whip_offer))
+        .route("/whip/:resource_id", axum::routing::delete(whip_delete))
+}
+
+/// WHEP: client POSTs an SDP offer, we answer and start streaming the ATC
+/// audio track. Returns the answer with a `Location` header per the
+/// `Link`-header resource pattern so the client can `DELETE` it later.
+async fn whep_offer(State(state): State<VoiceState>, offer_sdp: String) -> Response {
+    match negotiate_outbound(&state, offer_sdp).await {
+        Ok((resource_id, answer_sdp)) => sdp_response(answer_sdp, "/whep", &resource_id),
+        Err(e) => {
+            tracing::warn!("WHEP negotiation failed: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+async fn negotiate_outbound(
+    state: &VoiceState,
+    offer_sdp: String,
+) -> anyhow::Result<(String, String)> {
+    let pc = state.new_peer_connection().await?;
+    pc.add_track(state.atc_track.clone() as Arc<dyn webrtc::track::track_local::TrackLocal + Send + Sync>)
+        .await?;
+
+    let offer = RTCSessionDescription::offer(offer_sdp)?;
+    pc.set_remote_description(offer).await?;
+
+    let answer = pc.create_answer(None).await?;
+    pc.set_local_description(answer).await?;
+    let answer_sdp = gather_ice_candidates(&pc).await?;
+
+    let resource_id = state.insert_session(pc).await;
+    Ok((resource_id, answer_sdp))
+}
+
+/// WHIP: client POSTs an SDP offer carrying their microphone track. We
+/// answer, and once the remote track arrives we pause model heartbeats so
+/// they don't contend with the live pilot transmission.
+async fn whip_offer(State(state): State<VoiceState>, offer_sdp: String) -> Response {
+    match negotiate_inbound(&state, offer_sdp).await {
+        Ok((resource_id, answer_sdp)) => sdp_response(answer_sdp, "/whip", &resource_id),
+        Err(e) => {
+            tracing::warn!("WHIP negotiation failed: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+        }
+    }
+}
+
+async fn negotiate_inbound(
+    state: &VoiceState,
+    offer_sdp: String,
+) -> anyhow::Result<(String, String)> {
+    let pc = state.new_peer_connection().await?;
+
+    // Counts this connection once however many tracks it carries, and
+    // releases it once however many terminal states it passes through.
+    let counted = Arc::new(AtomicBool::new(false));
+
+    let warmup = state.warmup.clone();
+    let ingest_sessions = state.ingest_sessions.clone();
+    let track_counted = counted.clone();
+    pc.on_track(Box::new(move |_track: Arc<TrackRemote>, _, _| {
+        let warmup = warmup.clone();
+        let ingest_sessions = ingest_sessions.clone();
+        let counted = track_counted.clone();
+        Box::pin(async move {
+            if counted.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            let mut sessions = ingest_sessions.lock().await;
+            *sessions += 1;
+            warmup.pause();
+            tracing::info!("WHIP ingest started, warmup heartbeats paused");
+        })
+    }));
+
+    let warmup = state.warmup.clone();
+    let ingest_sessions = state.ingest_sessions.clone();
+    pc.on_peer_connection_state_change(Box::new(move |s| {
+        let warmup = warmup.clone();
+        let ingest_sessions = ingest_sessions.clone();
+        let counted = counted.clone();
+        Box::pin(async move {
+            if matches!(
+                s,
+                webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState::Disconnected
+                    | webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState::Closed
+                    | webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState::Failed
+            ) && counted.swap(false, Ordering::SeqCst)
+            {
+                let mut sessions = ingest_sessions.lock().await;
+                *sessions = sessions.saturating_sub(1);
+                if *sessions == 0 {
+                    warmup.resume();
+                    tracing::info!("WHIP ingest ended, warmup heartbeats resumed");
+                }
+            }
+        })
+    }));
+
+    let offer = RTCSessionDescription::offer(offer_sdp)?;
+    pc.set_remote_description(offer).await?;
+
+    let answer = pc.create_answer(None).await?;
+    pc.set_local_description(answer).await?;
+    let answer_sdp = gather_ice_candidates(&pc).await?;
+
+    let resource_id = state.insert_session(pc).await;
+    Ok((resource_id, answer_sdp))
+}
+
+/// Wait for ICE gathering to finish and return the fully-populated local
+/// SDP. `create_answer`'s SDP carries no ICE candidates yet - they're only
+/// attached to `pc.local_description()` once gathering completes - and
+/// these WHIP/WHEP endpoints answer with a single SDP rather than
+/// implementing trickle-ICE, so skipping this wait would hand the client an
+/// answer it can never actually connect with.
+async fn gather_ice_candidates(pc: &RTCPeerConnection) -> anyhow::Result<String> {
+    let mut gather_complete = pc.gathering_complete_promise().await;
+    let _ = gather_complete.recv().await;
+
+    let local_description = pc
+        .local_description()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("no local description set after ICE gathering"))?;
+    Ok(local_description.sdp)
+}
+
+/// Build the `201 Created` SDP answer response, with a `Location` header
+/// pointing at the resource id the session was just stored under so the
+/// client can `DELETE` it later to tear the connection down.
+fn sdp_response(answer_sdp: String, resource_path: &str, resource_id: &str) -> Response {
+    let location = format!("{resource_path}/{resource_id}");
+    (
+        StatusCode::CREATED,
+        [
+            (header::CONTENT_TYPE, "application/sdp".to_string()),
+            (header::LOCATION, location),
+        ],
+        answer_sdp,
+    )
+        .into_response()
+}
+
+async fn whep_delete(State(state): State<VoiceState>, Path(resource_id): Path<String>) -> StatusCode {
+    if state.close_session(&resource_id).await {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+async fn whip_delete(State(state): State<VoiceState>, Path(resource_id): Path<String>) -> StatusCode {
+    if state.close_session(&resource_id).await {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}